@@ -2,10 +2,12 @@ use std::arch::x86_64::{
     __m256, __m256d, __m256i, _mm256_add_pd, _mm256_add_ps, _mm256_and_pd, _mm256_and_ps,
     _mm256_andnot_pd, _mm256_andnot_ps, _mm256_castsi256_pd, _mm256_castsi256_ps, _mm256_cmp_pd,
     _mm256_cmp_ps, _mm256_cvtps_epi32, _mm256_div_pd, _mm256_div_ps, _mm256_floor_pd,
-    _mm256_floor_ps, _mm256_load_si256, _mm256_loadu_pd, _mm256_loadu_ps, _mm256_max_pd,
-    _mm256_max_ps, _mm256_min_pd, _mm256_min_ps, _mm256_mul_pd, _mm256_mul_ps, _mm256_or_pd,
-    _mm256_or_ps, _mm256_set1_epi32, _mm256_set1_epi64x, _mm256_set1_pd, _mm256_set1_ps,
-    _mm256_store_pd, _mm256_store_ps, _mm256_storeu_pd, _mm256_storeu_ps, _mm256_sub_pd,
+    _mm256_floor_ps, _mm256_fmadd_pd, _mm256_fmadd_ps, _mm256_load_si256, _mm256_loadu_pd,
+    _mm256_loadu_ps, _mm256_max_pd, _mm256_max_ps, _mm256_min_pd, _mm256_min_ps,
+    _mm256_movemask_pd, _mm256_movemask_ps, _mm256_mul_pd, _mm256_mul_ps, _mm256_or_pd,
+    _mm256_or_ps, _mm256_rcp_ps, _mm256_rsqrt_ps, _mm256_set1_epi32, _mm256_set1_epi64x,
+    _mm256_set1_pd, _mm256_set1_ps, _mm256_sqrt_pd, _mm256_sqrt_ps, _mm256_store_pd,
+    _mm256_store_ps, _mm256_storeu_pd, _mm256_storeu_ps, _mm256_sub_pd,
     _mm256_sub_ps, _mm256_xor_pd, _mm256_xor_ps, _CMP_EQ_OQ, _CMP_GT_OQ, _CMP_LT_OQ,
 };
 use std::mem;
@@ -74,6 +76,28 @@ impl SimdVector for AVXVector32 {
         _mm256_floor_ps(a)
     }
 
+    #[target_feature(enable = "avx")]
+    unsafe fn sqrt(a: Self::Float) -> Self::Float {
+        _mm256_sqrt_ps(a)
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn recip(a: Self::Float) -> Self::Float {
+        // One Newton-Raphson step y1 = y0 * (2 - x * y0) on top of the
+        // ~12-bit estimate lifts the result to near-full f32 precision.
+        let y0 = _mm256_rcp_ps(a);
+        Self::mul(y0, Self::sub(Self::splat(2.0), Self::mul(a, y0)))
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn rsqrt(a: Self::Float) -> Self::Float {
+        // One Newton-Raphson step y1 = y0 * (1.5 - 0.5 * x * y0 * y0) refines
+        // the ~12-bit estimate to near-full f32 precision.
+        let y0 = _mm256_rsqrt_ps(a);
+        let half_x = Self::mul(Self::splat(0.5), a);
+        Self::mul(y0, Self::sub(Self::splat(1.5), Self::mul(half_x, Self::mul(y0, y0))))
+    }
+
     #[target_feature(enable = "avx")]
     unsafe fn fma(a: Self::Float, b: Self::Float, c: Self::Float) -> Self::Float {
         _mm256_add_ps(_mm256_mul_ps(a, b), c)
@@ -94,6 +118,22 @@ impl SimdVector for AVXVector32 {
         _mm256_cmp_ps::<_CMP_LT_OQ>(a, b)
     }
 
+    #[target_feature(enable = "avx")]
+    unsafe fn movemask(mask: Self::Mask) -> u32 {
+        _mm256_movemask_ps(mask) as u32
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn any(mask: Self::Mask) -> bool {
+        Self::movemask(mask) != 0
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn all(mask: Self::Mask) -> bool {
+        // Eight lanes, so every sign bit set means the mask covers all of them.
+        Self::movemask(mask) == 0xff
+    }
+
     #[target_feature(enable = "avx")]
     unsafe fn mul(a: Self::Float, b: Self::Float) -> Self::Float {
         _mm256_mul_ps(a, b)
@@ -221,6 +261,22 @@ impl SimdVector for AVXVector64 {
         _mm256_floor_pd(a)
     }
 
+    #[target_feature(enable = "avx")]
+    unsafe fn sqrt(a: Self::Float) -> Self::Float {
+        _mm256_sqrt_pd(a)
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn recip(a: Self::Float) -> Self::Float {
+        // No fast-estimate instruction for f64, so divide straight out.
+        Self::div(Self::splat(1.0), a)
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn rsqrt(a: Self::Float) -> Self::Float {
+        Self::div(Self::splat(1.0), Self::sqrt(a))
+    }
+
     #[target_feature(enable = "avx")]
     unsafe fn fma(a: Self::Float, b: Self::Float, c: Self::Float) -> Self::Float {
         _mm256_add_pd(_mm256_mul_pd(a, b), c)
@@ -241,6 +297,22 @@ impl SimdVector for AVXVector64 {
         _mm256_cmp_pd::<_CMP_LT_OQ>(a, b)
     }
 
+    #[target_feature(enable = "avx")]
+    unsafe fn movemask(mask: Self::Mask) -> u32 {
+        _mm256_movemask_pd(mask) as u32
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn any(mask: Self::Mask) -> bool {
+        Self::movemask(mask) != 0
+    }
+
+    #[target_feature(enable = "avx")]
+    unsafe fn all(mask: Self::Mask) -> bool {
+        // Four lanes, so every sign bit set means the mask covers all of them.
+        Self::movemask(mask) == 0x0f
+    }
+
     #[target_feature(enable = "avx")]
     unsafe fn mul(a: Self::Float, b: Self::Float) -> Self::Float {
         _mm256_mul_pd(a, b)
@@ -316,3 +388,372 @@ impl SimdVector for AVXVector64 {
         super::apply_elementwise_generic(&v, f, f_rest, a);
     }
 }
+
+#[derive(Default)]
+pub struct AVXFMAVector32;
+
+impl SimdVector for AVXFMAVector32 {
+    type Lower = ScalarVector32;
+    type Float = __m256;
+    type FloatScalar = f32;
+    type FloatScalarArray = Aligned<
+        A32,
+        [Self::FloatScalar; mem::size_of::<Self::Float>() / mem::size_of::<Self::FloatScalar>()],
+    >;
+    type Int = __m256i;
+    type IntScalar = i32;
+    type Mask = __m256;
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn abs(a: Self::Float) -> Self::Float {
+        let mask = _mm256_set1_epi32(0x7fffffff);
+        _mm256_and_ps(a, _mm256_castsi256_ps(mask))
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn add(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_add_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn add_scalar(a: Self::Float, b: f32) -> Self::Float {
+        let b_simd = _mm256_set1_ps(b);
+        _mm256_add_ps(a, b_simd)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn bitwise_select(a: Self::Mask, b: Self::Float, c: Self::Float) -> Self::Float {
+        let u = _mm256_and_ps(a, b);
+        let v = _mm256_andnot_ps(a, c);
+        _mm256_or_ps(u, v)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn copy_sign(sign_src: Self::Float, dest: Self::Float) -> Self::Float {
+        // Negative zero has all bits unset, except the sign bit.
+        let sign_bit_mask = Self::splat(Self::FloatScalar::zero().neg());
+        Self::bitwise_select(sign_bit_mask, sign_src, dest)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn div(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_div_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn floor(a: Self::Float) -> Self::Float {
+        _mm256_floor_ps(a)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn sqrt(a: Self::Float) -> Self::Float {
+        _mm256_sqrt_ps(a)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn recip(a: Self::Float) -> Self::Float {
+        // One Newton-Raphson step y1 = y0 * (2 - x * y0) on top of the
+        // ~12-bit estimate lifts the result to near-full f32 precision.
+        let y0 = _mm256_rcp_ps(a);
+        Self::mul(y0, Self::sub(Self::splat(2.0), Self::mul(a, y0)))
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn rsqrt(a: Self::Float) -> Self::Float {
+        // One Newton-Raphson step y1 = y0 * (1.5 - 0.5 * x * y0 * y0) refines
+        // the ~12-bit estimate to near-full f32 precision.
+        let y0 = _mm256_rsqrt_ps(a);
+        let half_x = Self::mul(Self::splat(0.5), a);
+        Self::mul(y0, Self::sub(Self::splat(1.5), Self::mul(half_x, Self::mul(y0, y0))))
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn fma(a: Self::Float, b: Self::Float, c: Self::Float) -> Self::Float {
+        _mm256_fmadd_ps(a, b, c)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn eq(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm256_cmp_ps::<_CMP_EQ_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn gt(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm256_cmp_ps::<_CMP_GT_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn lt(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm256_cmp_ps::<_CMP_LT_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn movemask(mask: Self::Mask) -> u32 {
+        _mm256_movemask_ps(mask) as u32
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn any(mask: Self::Mask) -> bool {
+        Self::movemask(mask) != 0
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn all(mask: Self::Mask) -> bool {
+        // Eight lanes, so every sign bit set means the mask covers all of them.
+        Self::movemask(mask) == 0xff
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn mul(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_mul_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn mul_scalar(a: Self::Float, b: f32) -> Self::Float {
+        let b_simd = _mm256_set1_ps(b);
+        _mm256_mul_ps(a, b_simd)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn neg(a: Self::Float) -> Self::Float {
+        let neg_zero = _mm256_set1_ps(Self::FloatScalar::neg_zero());
+        _mm256_xor_ps(a, neg_zero)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn sub(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_sub_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn vmax(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_max_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn vmin(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_min_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn splat(v: f32) -> Self::Float {
+        _mm256_set1_ps(v)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn reinterpret_float_signed(v: Self::Int) -> Self::Float {
+        _mm256_castsi256_ps(v)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn to_int(v: Self::Float) -> Self::Int {
+        _mm256_cvtps_epi32(v)
+    }
+
+    unsafe fn to_float_scalar_array(v: Self::Float) -> Self::FloatScalarArray {
+        let mut a: Aligned<A32, _> = Aligned([0f32; 8]);
+        _mm256_store_ps(a.as_mut_ptr(), v);
+        a
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn with_load_store(f: &impl Fn(Self::Float) -> Self::Float, a: &mut [f32]) {
+        let mut val = _mm256_loadu_ps(a.as_ptr());
+        val = f(val);
+        _mm256_storeu_ps(a.as_mut_ptr(), val);
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn apply_elementwise(
+        f: impl Fn(Self::Float) -> Self::Float,
+        f_rest: impl Fn(&mut [f32]),
+        a: &mut [f32],
+    ) {
+        let v = Self;
+        super::apply_elementwise_generic(&v, f, f_rest, a);
+    }
+}
+
+#[derive(Default)]
+pub struct AVXFMAVector64;
+
+impl SimdVector for AVXFMAVector64 {
+    type Lower = ScalarVector64;
+    type Float = __m256d;
+    type FloatScalar = f64;
+    type FloatScalarArray = Aligned<
+        A32,
+        [Self::FloatScalar; mem::size_of::<Self::Float>() / mem::size_of::<Self::FloatScalar>()],
+    >;
+    type Int = __m256i;
+    type IntScalar = i64;
+    type Mask = __m256d;
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn abs(a: Self::Float) -> Self::Float {
+        let mask = _mm256_set1_epi64x(0x7fffffffffffffff);
+        _mm256_and_pd(a, _mm256_castsi256_pd(mask))
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn add(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_add_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn add_scalar(a: Self::Float, b: f64) -> Self::Float {
+        let b_simd = _mm256_set1_pd(b);
+        _mm256_add_pd(a, b_simd)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn bitwise_select(a: Self::Mask, b: Self::Float, c: Self::Float) -> Self::Float {
+        let u = _mm256_and_pd(a, b);
+        let v = _mm256_andnot_pd(a, c);
+        _mm256_or_pd(u, v)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn copy_sign(sign_src: Self::Float, dest: Self::Float) -> Self::Float {
+        // Negative zero has all bits unset, except the sign bit.
+        let sign_bit_mask = Self::splat(Self::FloatScalar::zero().neg());
+        Self::bitwise_select(sign_bit_mask, sign_src, dest)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn div(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_div_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn floor(a: Self::Float) -> Self::Float {
+        _mm256_floor_pd(a)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn sqrt(a: Self::Float) -> Self::Float {
+        _mm256_sqrt_pd(a)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn recip(a: Self::Float) -> Self::Float {
+        // No fast-estimate instruction for f64, so divide straight out.
+        Self::div(Self::splat(1.0), a)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn rsqrt(a: Self::Float) -> Self::Float {
+        Self::div(Self::splat(1.0), Self::sqrt(a))
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn fma(a: Self::Float, b: Self::Float, c: Self::Float) -> Self::Float {
+        _mm256_fmadd_pd(a, b, c)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn eq(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm256_cmp_pd::<_CMP_EQ_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn gt(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm256_cmp_pd::<_CMP_GT_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn lt(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm256_cmp_pd::<_CMP_LT_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn movemask(mask: Self::Mask) -> u32 {
+        _mm256_movemask_pd(mask) as u32
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn any(mask: Self::Mask) -> bool {
+        Self::movemask(mask) != 0
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn all(mask: Self::Mask) -> bool {
+        // Four lanes, so every sign bit set means the mask covers all of them.
+        Self::movemask(mask) == 0x0f
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn mul(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_mul_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn mul_scalar(a: Self::Float, b: f64) -> Self::Float {
+        let b_simd = _mm256_set1_pd(b);
+        _mm256_mul_pd(a, b_simd)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn neg(a: Self::Float) -> Self::Float {
+        let neg_zero = _mm256_set1_pd(Self::FloatScalar::neg_zero());
+        _mm256_xor_pd(a, neg_zero)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn sub(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_sub_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn vmax(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_max_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn vmin(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm256_min_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn splat(v: f64) -> Self::Float {
+        _mm256_set1_pd(v)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn reinterpret_float_signed(v: Self::Int) -> Self::Float {
+        _mm256_castsi256_pd(v)
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn to_int(v: Self::Float) -> Self::Int {
+        // Blegh, no instruction for this before AVX-512.
+        let mut data_f64: Aligned<A32, _> = Aligned([0f64; 4]);
+        _mm256_store_pd(data_f64.as_mut_ptr(), v);
+        let data = data_f64.map(|v| v as i64);
+        _mm256_load_si256(data.as_ptr().cast())
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn to_float_scalar_array(v: Self::Float) -> Self::FloatScalarArray {
+        let mut a: Aligned<A32, _> = Aligned([0f64; 4]);
+        _mm256_store_pd(a.as_mut_ptr(), v);
+        a
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn with_load_store(f: &impl Fn(Self::Float) -> Self::Float, a: &mut [f64]) {
+        let mut val = _mm256_loadu_pd(a.as_ptr());
+        val = f(val);
+        _mm256_storeu_pd(a.as_mut_ptr(), val);
+    }
+
+    #[target_feature(enable = "avx2,fma")]
+    unsafe fn apply_elementwise(
+        f: impl Fn(Self::Float) -> Self::Float,
+        f_rest: impl Fn(&mut [f64]),
+        a: &mut [f64],
+    ) {
+        let v = Self;
+        super::apply_elementwise_generic(&v, f, f_rest, a);
+    }
+}
@@ -0,0 +1,395 @@
+use std::arch::x86_64::{
+    __m512, __m512d, __m512i, __mmask8, __mmask16, _mm512_abs_pd, _mm512_abs_ps, _mm512_add_pd,
+    _mm512_add_ps, _mm512_and_si512, _mm512_andnot_si512, _mm512_castpd_si512, _mm512_castps_si512,
+    _mm512_castsi512_pd, _mm512_castsi512_ps, _mm512_cmp_pd_mask, _mm512_cmp_ps_mask,
+    _mm512_cvtps_epi32, _mm512_cvttpd_epi64, _mm512_div_pd, _mm512_div_ps, _mm512_fmadd_pd,
+    _mm512_fmadd_ps, _mm512_loadu_pd, _mm512_loadu_ps, _mm512_mask_blend_pd, _mm512_mask_blend_ps,
+    _mm512_max_pd, _mm512_max_ps, _mm512_min_pd, _mm512_min_ps, _mm512_mul_pd, _mm512_mul_ps,
+    _mm512_or_si512, _mm512_rcp14_ps, _mm512_roundscale_pd, _mm512_roundscale_ps, _mm512_rsqrt14_ps,
+    _mm512_set1_epi32, _mm512_set1_epi64, _mm512_set1_pd, _mm512_set1_ps, _mm512_sqrt_pd,
+    _mm512_sqrt_ps, _mm512_store_pd, _mm512_store_ps, _mm512_storeu_pd, _mm512_storeu_ps,
+    _mm512_sub_pd, _mm512_sub_ps, _mm512_xor_si512, _CMP_EQ_OQ, _CMP_GT_OQ, _CMP_LT_OQ,
+};
+use std::mem;
+use std::ops::Neg;
+
+use aligned::{Aligned, A64};
+use num_traits::{Float, Zero};
+
+use super::scalar::{ScalarVector32, ScalarVector64};
+use super::SimdVector;
+
+// Round towards negative infinity, suppressing the inexact exception
+// (`_MM_FROUND_TO_NEG_INF | _MM_FROUND_NO_EXC`). Used to implement `floor`,
+// which has no dedicated AVX-512 instruction.
+const ROUND_FLOOR: i32 = 0x09;
+
+#[derive(Default)]
+pub struct AVX512Vector32;
+
+impl SimdVector for AVX512Vector32 {
+    type Lower = ScalarVector32;
+    type Float = __m512;
+    type FloatScalar = f32;
+    type FloatScalarArray = Aligned<
+        A64,
+        [Self::FloatScalar; mem::size_of::<Self::Float>() / mem::size_of::<Self::FloatScalar>()],
+    >;
+    type Int = __m512i;
+    type IntScalar = i32;
+    type Mask = __mmask16;
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn abs(a: Self::Float) -> Self::Float {
+        _mm512_abs_ps(a)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn add(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_add_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn add_scalar(a: Self::Float, b: f32) -> Self::Float {
+        let b_simd = _mm512_set1_ps(b);
+        _mm512_add_ps(a, b_simd)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn bitwise_select(a: Self::Mask, b: Self::Float, c: Self::Float) -> Self::Float {
+        // Mask registers blend whole lanes: keep `b` where the mask bit is set,
+        // `c` otherwise.
+        _mm512_mask_blend_ps(a, c, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn copy_sign(sign_src: Self::Float, dest: Self::Float) -> Self::Float {
+        // Mask registers can't address the sign bit directly, so splice it in
+        // with integer bit operations.
+        let sign_mask = _mm512_set1_epi32(0x80000000u32 as i32);
+        let signs = _mm512_and_si512(_mm512_castps_si512(sign_src), sign_mask);
+        let magnitude = _mm512_andnot_si512(sign_mask, _mm512_castps_si512(dest));
+        _mm512_castsi512_ps(_mm512_or_si512(signs, magnitude))
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn div(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_div_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn floor(a: Self::Float) -> Self::Float {
+        _mm512_roundscale_ps::<ROUND_FLOOR>(a)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn sqrt(a: Self::Float) -> Self::Float {
+        _mm512_sqrt_ps(a)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn recip(a: Self::Float) -> Self::Float {
+        // One Newton-Raphson step y1 = y0 * (2 - x * y0) on top of the
+        // ~14-bit estimate lifts the result to near-full f32 precision.
+        let y0 = _mm512_rcp14_ps(a);
+        Self::mul(y0, Self::sub(Self::splat(2.0), Self::mul(a, y0)))
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn rsqrt(a: Self::Float) -> Self::Float {
+        // One Newton-Raphson step y1 = y0 * (1.5 - 0.5 * x * y0 * y0) refines
+        // the ~14-bit estimate to near-full f32 precision.
+        let y0 = _mm512_rsqrt14_ps(a);
+        let half_x = Self::mul(Self::splat(0.5), a);
+        Self::mul(y0, Self::sub(Self::splat(1.5), Self::mul(half_x, Self::mul(y0, y0))))
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn fma(a: Self::Float, b: Self::Float, c: Self::Float) -> Self::Float {
+        _mm512_fmadd_ps(a, b, c)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn eq(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm512_cmp_ps_mask::<_CMP_EQ_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn gt(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm512_cmp_ps_mask::<_CMP_GT_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn lt(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm512_cmp_ps_mask::<_CMP_LT_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn movemask(mask: Self::Mask) -> u32 {
+        mask as u32
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn any(mask: Self::Mask) -> bool {
+        mask != 0
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn all(mask: Self::Mask) -> bool {
+        // Sixteen lanes, so every mask bit set means the mask covers all of them.
+        mask == 0xffff
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn mul(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_mul_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn mul_scalar(a: Self::Float, b: f32) -> Self::Float {
+        let b_simd = _mm512_set1_ps(b);
+        _mm512_mul_ps(a, b_simd)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn neg(a: Self::Float) -> Self::Float {
+        let neg_zero = _mm512_set1_epi32(0x80000000u32 as i32);
+        _mm512_castsi512_ps(_mm512_xor_si512(_mm512_castps_si512(a), neg_zero))
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn sub(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_sub_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn vmax(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_max_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn vmin(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_min_ps(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn splat(v: f32) -> Self::Float {
+        _mm512_set1_ps(v)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn reinterpret_float_signed(v: Self::Int) -> Self::Float {
+        _mm512_castsi512_ps(v)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn to_int(v: Self::Float) -> Self::Int {
+        _mm512_cvtps_epi32(v)
+    }
+
+    unsafe fn to_float_scalar_array(v: Self::Float) -> Self::FloatScalarArray {
+        let mut a: Aligned<A64, _> = Aligned([0f32; 16]);
+        _mm512_store_ps(a.as_mut_ptr(), v);
+        a
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn with_load_store(f: &impl Fn(Self::Float) -> Self::Float, a: &mut [f32]) {
+        let mut val = _mm512_loadu_ps(a.as_ptr());
+        val = f(val);
+        _mm512_storeu_ps(a.as_mut_ptr(), val);
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn apply_elementwise(
+        f: impl Fn(Self::Float) -> Self::Float,
+        f_rest: impl Fn(&mut [f32]),
+        a: &mut [f32],
+    ) {
+        let v = Self;
+        super::apply_elementwise_generic(&v, f, f_rest, a);
+    }
+}
+
+#[derive(Default)]
+pub struct AVX512Vector64;
+
+impl SimdVector for AVX512Vector64 {
+    type Lower = ScalarVector64;
+    type Float = __m512d;
+    type FloatScalar = f64;
+    type FloatScalarArray = Aligned<
+        A64,
+        [Self::FloatScalar; mem::size_of::<Self::Float>() / mem::size_of::<Self::FloatScalar>()],
+    >;
+    type Int = __m512i;
+    type IntScalar = i64;
+    type Mask = __mmask8;
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn abs(a: Self::Float) -> Self::Float {
+        _mm512_abs_pd(a)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn add(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_add_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn add_scalar(a: Self::Float, b: f64) -> Self::Float {
+        let b_simd = _mm512_set1_pd(b);
+        _mm512_add_pd(a, b_simd)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn bitwise_select(a: Self::Mask, b: Self::Float, c: Self::Float) -> Self::Float {
+        // Mask registers blend whole lanes: keep `b` where the mask bit is set,
+        // `c` otherwise.
+        _mm512_mask_blend_pd(a, c, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn copy_sign(sign_src: Self::Float, dest: Self::Float) -> Self::Float {
+        // Mask registers can't address the sign bit directly, so splice it in
+        // with integer bit operations.
+        let sign_mask = _mm512_set1_epi64(0x8000000000000000u64 as i64);
+        let signs = _mm512_and_si512(_mm512_castpd_si512(sign_src), sign_mask);
+        let magnitude = _mm512_andnot_si512(sign_mask, _mm512_castpd_si512(dest));
+        _mm512_castsi512_pd(_mm512_or_si512(signs, magnitude))
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn div(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_div_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn floor(a: Self::Float) -> Self::Float {
+        _mm512_roundscale_pd::<ROUND_FLOOR>(a)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn sqrt(a: Self::Float) -> Self::Float {
+        _mm512_sqrt_pd(a)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn recip(a: Self::Float) -> Self::Float {
+        // No fast-estimate instruction for f64, so divide straight out.
+        Self::div(Self::splat(1.0), a)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn rsqrt(a: Self::Float) -> Self::Float {
+        Self::div(Self::splat(1.0), Self::sqrt(a))
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn fma(a: Self::Float, b: Self::Float, c: Self::Float) -> Self::Float {
+        _mm512_fmadd_pd(a, b, c)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn eq(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm512_cmp_pd_mask::<_CMP_EQ_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn gt(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm512_cmp_pd_mask::<_CMP_GT_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn lt(a: Self::Float, b: Self::Float) -> Self::Mask {
+        _mm512_cmp_pd_mask::<_CMP_LT_OQ>(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn movemask(mask: Self::Mask) -> u32 {
+        mask as u32
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn any(mask: Self::Mask) -> bool {
+        mask != 0
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn all(mask: Self::Mask) -> bool {
+        // Eight lanes, so every mask bit set means the mask covers all of them.
+        mask == 0xff
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn mul(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_mul_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn mul_scalar(a: Self::Float, b: f64) -> Self::Float {
+        let b_simd = _mm512_set1_pd(b);
+        _mm512_mul_pd(a, b_simd)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn neg(a: Self::Float) -> Self::Float {
+        let neg_zero = _mm512_set1_epi64(0x8000000000000000u64 as i64);
+        _mm512_castsi512_pd(_mm512_xor_si512(_mm512_castpd_si512(a), neg_zero))
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn sub(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_sub_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn vmax(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_max_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn vmin(a: Self::Float, b: Self::Float) -> Self::Float {
+        _mm512_min_pd(a, b)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn splat(v: f64) -> Self::Float {
+        _mm512_set1_pd(v)
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn reinterpret_float_signed(v: Self::Int) -> Self::Float {
+        _mm512_castsi512_pd(v)
+    }
+
+    #[target_feature(enable = "avx512f,avx512dq")]
+    unsafe fn to_int(v: Self::Float) -> Self::Int {
+        // Unlike AVX, AVX-512 has a native float-to-int64 conversion, but
+        // `VCVTTPD2QQ` lives in the AVX-512DQ subset rather than the F base.
+        _mm512_cvttpd_epi64(v)
+    }
+
+    unsafe fn to_float_scalar_array(v: Self::Float) -> Self::FloatScalarArray {
+        let mut a: Aligned<A64, _> = Aligned([0f64; 8]);
+        _mm512_store_pd(a.as_mut_ptr(), v);
+        a
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn with_load_store(f: &impl Fn(Self::Float) -> Self::Float, a: &mut [f64]) {
+        let mut val = _mm512_loadu_pd(a.as_ptr());
+        val = f(val);
+        _mm512_storeu_pd(a.as_mut_ptr(), val);
+    }
+
+    #[target_feature(enable = "avx512f")]
+    unsafe fn apply_elementwise(
+        f: impl Fn(Self::Float) -> Self::Float,
+        f_rest: impl Fn(&mut [f64]),
+        a: &mut [f64],
+    ) {
+        let v = Self;
+        super::apply_elementwise_generic(&v, f, f_rest, a);
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::OnceLock;
+
+/// The vector instruction set that runtime dispatch has selected for this
+/// process.
+///
+/// Variants are ordered from narrowest to widest, so later variants are
+/// always preferable when the CPU supports them. [`AVX512Vector32`] is the
+/// widest backend and is selected ahead of the others when the CPU supports
+/// both AVX-512F and AVX-512DQ.
+///
+/// [`AVX512Vector32`]: super::avx512::AVX512Vector32
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Isa {
+    Scalar,
+    Neon,
+    Avx,
+    AvxFma,
+    Avx512,
+}
+
+static ISA: OnceLock<Isa> = OnceLock::new();
+
+/// Detect the widest vector instruction set supported by the current CPU.
+///
+/// The probe runs once and the result is cached, so repeated calls are a
+/// cheap atomic load. This mirrors the compile-time `target_feature`
+/// selection, but performed at runtime so that a single generic build runs
+/// optimally across machines.
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+pub fn detect_isa() -> Isa {
+    *ISA.get_or_init(|| {
+        if is_x86_feature_detected!("avx512f") && is_x86_feature_detected!("avx512dq") {
+            // AVX512Vector64::to_int relies on VCVTTPD2QQ, which is in the DQ
+            // subset, so require it before selecting the AVX-512 backend.
+            Isa::Avx512
+        } else if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            Isa::AvxFma
+        } else if is_x86_feature_detected!("avx") {
+            Isa::Avx
+        } else {
+            Isa::Scalar
+        }
+    })
+}
+
+#[cfg(target_arch = "aarch64")]
+pub fn detect_isa() -> Isa {
+    *ISA.get_or_init(|| {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            Isa::Neon
+        } else {
+            Isa::Scalar
+        }
+    })
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+pub fn detect_isa() -> Isa {
+    *ISA.get_or_init(|| Isa::Scalar)
+}
+
+/// Dispatch an elementwise kernel to the widest backend the CPU supports.
+///
+/// The kernel must be a function that is generic over [`SimdVector`], since
+/// each backend uses a distinct `Float` type. The macro monomorphizes it for
+/// the selected backend and forwards the remaining arguments:
+///
+/// ```ignore
+/// unsafe fn exp<V: SimdVector>(a: &mut [V::FloatScalar]) { /* ... */ }
+/// dispatch_elementwise!(f32, exp, xs);
+/// ```
+///
+/// [`SimdVector`]: super::SimdVector
+#[macro_export]
+macro_rules! dispatch_elementwise {
+    (f32, $kernel:path, $($arg:expr),* $(,)?) => {{
+        use $crate::vector::dispatch::{detect_isa, Isa};
+        match detect_isa() {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Avx512 => $kernel::<$crate::vector::avx512::AVX512Vector32>($($arg),*),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::AvxFma => $kernel::<$crate::vector::avx::AVXFMAVector32>($($arg),*),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Avx => $kernel::<$crate::vector::avx::AVXVector32>($($arg),*),
+            #[cfg(target_arch = "aarch64")]
+            Isa::Neon => $kernel::<$crate::vector::aarch64::NeonVector32>($($arg),*),
+            _ => $kernel::<$crate::vector::scalar::ScalarVector32>($($arg),*),
+        }
+    }};
+    (f64, $kernel:path, $($arg:expr),* $(,)?) => {{
+        use $crate::vector::dispatch::{detect_isa, Isa};
+        match detect_isa() {
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Avx512 => $kernel::<$crate::vector::avx512::AVX512Vector64>($($arg),*),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::AvxFma => $kernel::<$crate::vector::avx::AVXFMAVector64>($($arg),*),
+            #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+            Isa::Avx => $kernel::<$crate::vector::avx::AVXVector64>($($arg),*),
+            #[cfg(target_arch = "aarch64")]
+            Isa::Neon => $kernel::<$crate::vector::aarch64::NeonVector64>($($arg),*),
+            _ => $kernel::<$crate::vector::scalar::ScalarVector64>($($arg),*),
+        }
+    }};
+}